@@ -28,6 +28,86 @@ fn wyrand_benchmark(c: &mut Criterion) {
             criterion::BatchSize::LargeInput,
         )
     });
+
+    c.bench_function("fill_u64", |b| {
+        let mut rng = WyRand::new(123456);
+
+        let data = [0u64; 256];
+
+        b.iter_batched_ref(
+            || data,
+            |data| {
+                rng.fill_u64(data);
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("fill_u64_naive", |b| {
+        let mut rng = WyRand::new(123456);
+
+        let data = [0u64; 256];
+
+        b.iter_batched_ref(
+            || data,
+            |data| {
+                data.iter_mut().for_each(|value| *value = rng.rand());
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("gen_range_small", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| black_box(rng.gen_range(black_box(1..=6))));
+    });
+
+    c.bench_function("gen_range_large", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| black_box(rng.gen_range(black_box(0..u64::MAX / 3 * 2))));
+    });
+
+    c.bench_function("index_small", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| black_box(rng.index(black_box(6))));
+    });
+
+    c.bench_function("index_large", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| black_box(rng.index(black_box(usize::MAX / 3 * 2))));
+    });
+
+    c.bench_function("range_step_iter", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| {
+            for value in rng.range_step_iter(black_box(0..1000), 100) {
+                black_box(value);
+            }
+        });
+    });
+
+    c.bench_function("shuffle_1000", |b| {
+        let mut rng = WyRand::new(123456);
+
+        let mut data = [0u32; 1000];
+        data.iter_mut()
+            .enumerate()
+            .for_each(|(i, value)| *value = i as u32);
+
+        b.iter(|| rng.shuffle(black_box(&mut data)));
+    });
+
+    #[cfg(feature = "alloc")]
+    c.bench_function("random_bijection_1000", |b| {
+        let mut rng = WyRand::new(123456);
+
+        b.iter(|| black_box(rng.random_bijection(black_box(1000))));
+    });
 }
 
 pub fn benches() {
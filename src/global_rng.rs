@@ -0,0 +1,142 @@
+//! `fastrand` style functions backed by a thread-local [`WyRand`] instance.
+
+use core::cell::RefCell;
+use core::ops::{Bound, RangeBounds};
+
+use crate::WyRand;
+
+std::thread_local! {
+    static RNG: RefCell<WyRand> = RefCell::new(WyRand::from_time());
+}
+
+#[inline]
+fn with_rng<R>(f: impl FnOnce(&mut WyRand) -> R) -> R {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Converts a [`usize`](prim@usize) bound, mapping an unbounded side to `limit` so
+/// values never exceed the platform's [`usize`](prim@usize) width.
+#[inline]
+fn to_u64_bound(bound: Bound<&usize>, limit: usize) -> Bound<u64> {
+    match bound {
+        Bound::Included(&value) => Bound::Included(value as u64),
+        Bound::Excluded(&value) => Bound::Excluded(value as u64),
+        Bound::Unbounded => Bound::Included(limit as u64),
+    }
+}
+
+/// Seeds the thread-local generator, making all subsequent calls on the current
+/// thread deterministic.
+#[inline]
+pub fn seed(seed: u64) {
+    with_rng(|rng| *rng = WyRand::new(seed));
+}
+
+/// Generates a random [`u64`](prim@u64) value within the provided range, using the
+/// thread-local generator.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+#[inline]
+pub fn u64(range: impl RangeBounds<u64>) -> u64 {
+    with_rng(|rng| rng.gen_range(range))
+}
+
+/// Generates a random [`usize`](prim@usize) value within the provided range, using the
+/// thread-local generator.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+#[inline]
+pub fn usize(range: impl RangeBounds<usize>) -> usize {
+    let range = (
+        to_u64_bound(range.start_bound(), usize::MIN),
+        to_u64_bound(range.end_bound(), usize::MAX),
+    );
+
+    with_rng(|rng| rng.gen_range(range) as usize)
+}
+
+/// Generates a random [`bool`](prim@bool) value, using the thread-local generator.
+#[inline]
+pub fn bool() -> bool {
+    with_rng(|rng| rng.random())
+}
+
+/// Generates a random [`f64`](prim@f64) value in the half-open range `[0, 1)`, using the
+/// thread-local generator.
+#[inline]
+pub fn f64() -> f64 {
+    with_rng(WyRand::gen_f64)
+}
+
+/// Shuffles the slice in place, using the thread-local generator.
+#[inline]
+pub fn shuffle<T>(slice: &mut [T]) {
+    with_rng(|rng| rng.shuffle(slice));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    #[test]
+    fn global_functions() {
+        for _ in 0..100 {
+            assert!((10..20).contains(&super::u64(10..20)));
+            assert!((10..=20).contains(&super::usize(10..=20)));
+            assert!((0.0..1.0).contains(&super::f64()));
+        }
+
+        assert_eq!(super::u64(5..=5), 5);
+        assert_eq!(super::usize(..1), 0);
+        assert!(
+            (0..100).all(|_| super::usize(5..) >= 5),
+            "open-ended ranges should respect the lower bound"
+        );
+        assert_eq!(super::usize(usize::MAX..), usize::MAX);
+
+        let flips: Vec<bool> = (0..100).map(|_| super::bool()).collect();
+
+        assert!(
+            flips.contains(&true) && flips.contains(&false),
+            "both outcomes should appear over 100 flips"
+        );
+
+        let mut values: Vec<u32> = (0..50).collect();
+
+        super::shuffle(&mut values);
+        values.sort_unstable();
+
+        assert!(
+            values.into_iter().eq(0..50),
+            "shuffling should preserve all elements"
+        );
+    }
+
+    #[test]
+    fn seed_is_deterministic() {
+        let run = || {
+            super::seed(42);
+
+            let mut values: [u32; 16] = core::array::from_fn(|i| i as u32);
+            super::shuffle(&mut values);
+
+            (
+                super::u64(..),
+                super::usize(0..100),
+                super::bool(),
+                super::f64(),
+                values,
+            )
+        };
+
+        assert_eq!(
+            run(),
+            run(),
+            "seeding should make subsequent calls deterministic"
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //! fast and performant while still having great statistical properties.
 //!
 //! This crate can be used on its own or be integrated with `rand_core`/`rand`, and it is
-//! `no-std` compatible. Minimum compatible Rust version is 1.60.
+//! `no-std` compatible. Minimum compatible Rust version is 1.65.
 //!
 //! # Example
 //!
@@ -27,24 +27,108 @@
 //! & `debug` features enabled.
 //!
 //! * **`rand_core`** - Enables support for `rand_core`, implementing `RngCore` &
-//!   `SeedableRng` on [`WyRand`].
+//!   `SeedableRng` on [`WyRand`]. This also makes the extension methods of `rand::Rng`,
+//!   like `fill`, available on [`WyRand`] when using the full `rand` crate.
 //! * **`debug`** - Enables [`core::fmt::Debug`] implementation for [`WyRand`].
-//! * **`serde1`** - Enables `Serialize` and `Deserialize` derives on [`WyRand`].
+//! * **`serde1`** - Enables `Serialize` and `Deserialize` derives on [`WyRand`], which is
+//!   serialized as its bare [`u64`](prim@u64) state.
 //! * **`hash`** - Enables [`core::hash::Hash`] implementation for [`WyRand`].
+//! * **`alloc`** - Enables methods on [`WyRand`] that return heap allocated collections.
+//! * **`std`** - Enables methods on [`WyRand`] that depend on the standard library, along with
+//!   `fastrand` style global functions like `wyrand::u64` and `wyrand::shuffle` that are backed by
+//!   a thread-local [`WyRand`]. Implies `alloc`.
+//! * **`entropy`** - Enables seeding [`WyRand`] from OS entropy via `getrandom`.
 #![warn(missing_docs, rust_2018_idioms)]
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
 #[cfg(feature = "debug")]
 use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::num::{NonZeroU32, NonZeroU64};
+use core::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
+
+#[cfg(feature = "std")]
+mod global_rng;
+
+#[cfg(feature = "std")]
+pub use global_rng::*;
 
 #[cfg(feature = "rand_core")]
-use rand_core::{impls::fill_bytes_via_next, RngCore, SeedableRng};
+use rand_core::{RngCore, SeedableRng};
 
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
+const WY0: u64 = 0xa076_1d64_78bd_642f;
+const WY1: u64 = 0xe703_7ed1_a0b4_28db;
+
+/// Multiplies two [`u64`](prim@u64) values into a [`u128`], returning the low and high
+/// halves of the product as `(low, high)`.
+///
+/// ```
+/// use wyrand::wymul;
+///
+/// const PRODUCT: (u64, u64) = wymul(u64::MAX, 2);
+///
+/// assert_eq!(PRODUCT, (u64::MAX - 1, 1));
+/// ```
+#[inline]
+#[must_use]
+pub const fn wymul(a: u64, b: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_mul(b as u128);
+    (t as u64, t.wrapping_shr(64) as u64)
+}
+
+/// Multiplies two [`u64`](prim@u64) values into a [`u128`] and folds the halves together
+/// with XOR. This is the mixing step at the heart of [`WyRand`].
+///
+/// ```
+/// use wyrand::{wymix, wymul};
+///
+/// const MIXED: u64 = wymix(u64::MAX, 2);
+///
+/// let (low, high) = wymul(u64::MAX, 2);
+///
+/// assert_eq!(MIXED, low ^ high);
+/// assert_eq!(MIXED, u64::MAX);
+/// ```
+#[inline]
+#[must_use]
+pub const fn wymix(a: u64, b: u64) -> u64 {
+    let (low, high) = wymul(a, b);
+    low ^ high
+}
+
+/// A single step of the WyRand algorithm, returning the advanced state along with
+/// the value generated from it.
+#[inline(always)]
+const fn wyrand_step(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(WY0);
+
+    (state, wymix(state, state ^ WY1))
+}
+
+/// Sources a random [`u64`](prim@u64) from the OS entropy source.
+#[cfg(feature = "entropy")]
+#[inline]
+fn get_random_u64() -> Result<u64, getrandom::Error> {
+    let mut bytes = [0u8; core::mem::size_of::<u64>()];
+
+    getrandom::getrandom(&mut bytes)?;
+
+    Ok(u64::from_ne_bytes(bytes))
+}
+
 /// A Pseudorandom Number generator, powered by the `wyrand` algorithm.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(transparent))]
 #[cfg_attr(feature = "hash", derive(Hash))]
 #[repr(transparent)]
 pub struct WyRand {
@@ -52,6 +136,9 @@ pub struct WyRand {
 }
 
 impl WyRand {
+    /// The fixed seed used by [`WyRand::default_seeded`].
+    pub const DEFAULT_SEED: u64 = 0xDEAD_BEEF;
+
     /// Creates a new [`WyRand`] instance with the provided seed. Be sure
     /// to obtain the seed value from a good entropy source, either from
     /// hardware, OS source, or from a suitable crate, like `getrandom`.
@@ -61,133 +148,2902 @@ impl WyRand {
         Self { state }
     }
 
-    /// Generates a random [`u64`] value and advances the PRNG state.
+    /// Replaces the internal state with `seed`, as if the instance had just been
+    /// created with [`WyRand::new`].
+    #[inline]
+    pub fn reseed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    /// Returns the current internal state. Restoring it with [`WyRand::reseed`]
+    /// or [`WyRand::new`] reproduces the subsequent stream of values exactly.
+    #[inline]
+    #[must_use]
+    pub fn current_state(&self) -> u64 {
+        self.state
+    }
+
+    /// Advances the PRNG state by `n` draws in constant time, without computing the
+    /// skipped outputs. Following this with [`WyRand::rand`] yields the same value as
+    /// the `n + 1`th call to [`WyRand::rand`] would have.
+    #[inline]
+    pub fn discard(&mut self, n: u64) {
+        self.state = self.state.wrapping_add(WY0.wrapping_mul(n));
+    }
+
+    /// Fills `dest` with generated [`u64`](prim@u64) values, producing the same values
+    /// as calling [`WyRand::rand`] for each element in turn. The state is kept in a
+    /// local for the duration of the fill, so it can stay in a register.
+    #[inline]
+    pub fn fill_u64(&mut self, dest: &mut [u64]) {
+        let mut state = self.state;
+
+        for slot in dest {
+            (state, *slot) = wyrand_step(state);
+        }
+
+        self.state = state;
+    }
+
+    /// Creates a new [`WyRand`] instance seeded from any [`RngCore`] source. Unlike
+    /// [`SeedableRng::from_rng`], the drawn seed is passed through an extra mixing
+    /// step, avoiding correlated streams when spawning many generators from one source.
+    #[cfg(feature = "rand_core")]
+    #[inline]
+    #[must_use]
+    pub fn spawn<R: RngCore>(rng: &mut R) -> Self {
+        Self::from_mixed_seed(rng.next_u64())
+    }
+
+    /// Creates a new, independent child [`WyRand`] instance, seeded from a value
+    /// drawn from this generator and passed through an extra mixing step. Forks are
+    /// reproducible from the parent's state, but statistically decorrelated from it.
+    ///
+    /// Forking advances the parent's state by one draw.
+    #[inline]
+    #[must_use]
+    pub fn fork(&mut self) -> Self {
+        Self::from_mixed_seed(self.rand())
+    }
+
+    #[inline]
+    fn from_mixed_seed(seed: u64) -> Self {
+        Self::new(wymix(seed ^ WY0, WY1))
+    }
+
+    /// Creates a new [`WyRand`] instance with the fixed [`WyRand::DEFAULT_SEED`],
+    /// giving tests and examples an obvious reproducible starting generator.
+    #[inline]
+    #[must_use]
+    pub fn default_seeded() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+
+    /// Generates a random [`u64`](prim@u64) value and advances the PRNG state.
     #[inline]
     pub fn rand(&mut self) -> u64 {
-        self.state = self.state.wrapping_add(0xa076_1d64_78bd_642f);
-        let t = u128::from(self.state).wrapping_mul(u128::from(self.state ^ 0xe703_7ed1_a0b4_28db));
-        (t.wrapping_shr(64) ^ t) as u64
+        let (state, value) = wyrand_step(self.state);
+        self.state = state;
+
+        value
     }
-}
 
-#[cfg(feature = "debug")]
-impl Debug for WyRand {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("WyRand").finish()
+    /// Generates a random value of any type implementing [`Random`].
+    ///
+    /// ```rust
+    /// use wyrand::WyRand;
+    ///
+    /// let mut rng = WyRand::new(Default::default());
+    ///
+    /// let (id, flag, weight): (u32, bool, f64) = rng.random();
+    /// ```
+    #[inline]
+    pub fn random<T: Random>(&mut self) -> T {
+        T::random(self)
     }
-}
 
-#[cfg(feature = "rand_core")]
-impl RngCore for WyRand {
+    /// Generates an array of `N` random values of any type implementing [`Random`],
+    /// without allocating. Elements are generated in order, from first to last.
     #[inline]
-    fn next_u32(&mut self) -> u32 {
-        self.rand() as u32
+    pub fn collect_array<T: Random, const N: usize>(&mut self) -> [T; N] {
+        [(); N].map(|_| T::random(self))
     }
 
+    /// Generates a random [`NonZeroU64`] value, rejecting any generated zeroes.
     #[inline]
-    fn next_u64(&mut self) -> u64 {
-        self.rand()
+    pub fn gen_nonzero_u64(&mut self) -> NonZeroU64 {
+        loop {
+            if let Some(value) = NonZeroU64::new(self.rand()) {
+                return value;
+            }
+        }
     }
 
+    /// Generates a random [`NonZeroU32`] value from the high bits of generated
+    /// values, rejecting any zeroes.
     #[inline]
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        fill_bytes_via_next(self, dest);
+    pub fn gen_nonzero_u32(&mut self) -> NonZeroU32 {
+        loop {
+            if let Some(value) = NonZeroU32::new((self.rand() >> 32) as u32) {
+                return value;
+            }
+        }
     }
 
+    /// Generates a random value of `bits` width, taken from the high bits of a
+    /// generated [`u64`](prim@u64) value. `bits` must be within `1..=64`.
     #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    pub fn next_bits(&mut self, bits: u32) -> u64 {
+        debug_assert!((1..=64).contains(&bits), "bits must be within 1..=64");
+
+        self.rand() >> (64 - bits)
     }
-}
 
-#[cfg(feature = "rand_core")]
-impl SeedableRng for WyRand {
-    type Seed = [u8; core::mem::size_of::<u64>()];
+    /// Generates a [`bool`](prim@bool) that is `true` with probability `p`, which
+    /// should be within `0.0..=1.0`. A `p` of `0.0` always returns `false`, and a `p`
+    /// of `1.0` always returns `true`.
+    #[inline]
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        debug_assert!((0.0..=1.0).contains(&p), "p must be within 0.0..=1.0");
 
-    fn from_seed(seed: Self::Seed) -> Self {
-        Self::new(u64::from_ne_bytes(seed))
+        if p >= 1.0 {
+            return true;
+        }
+
+        // Scale the probability onto the full range of generated values, so the
+        // comparison is done in integers. A `p` of zero gives a threshold of zero.
+        let threshold = (p * (1u128 << 64) as f64) as u64;
+
+        self.rand() < threshold
     }
-}
 
-#[cfg(test)]
-mod tests {
-    extern crate alloc;
+    /// Generates a random [`f64`](prim@f64) value in the half-open range `[0, 1)`, using the
+    /// top 53 bits of a generated [`u64`](prim@u64) value scaled by `2^-53`. Every
+    /// multiple of `2^-53` in the range is equally likely, and the result is always
+    /// `>= 0.0` and `< 1.0`.
+    #[inline]
+    pub fn gen_f64(&mut self) -> f64 {
+        (self.rand() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
 
-    use alloc::format;
+    /// Generates a random [`f32`] value in the half-open range `[0, 1)`, using the
+    /// top 24 bits of a generated [`u64`](prim@u64) value scaled by `2^-24`. Every
+    /// multiple of `2^-24` in the range is equally likely, and the result is always
+    /// `>= 0.0` and `< 1.0`.
+    #[inline]
+    pub fn gen_f32(&mut self) -> f32 {
+        (self.rand() >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
 
-    use super::*;
+    /// Generates an antithetic pair `(u, 1.0 - u)` from a single draw, with `u` in
+    /// `[0, 1)`. The two components are perfectly negatively correlated, so for
+    /// monotonic integrands averaging over both halves of each pair reduces the
+    /// variance of Monte Carlo estimates compared to independent draws.
+    #[inline]
+    pub fn rand_f64_antithetic(&mut self) -> (f64, f64) {
+        let value = self.gen_f64();
 
-    #[cfg(feature = "debug")]
-    #[test]
-    fn no_leaking_debug() {
-        let rng = WyRand::new(Default::default());
+        (value, 1.0 - value)
+    }
 
-        assert_eq!(
-            format!("{rng:?}"),
-            "WyRand",
-            "Debug should not be leaking internal state"
-        );
+    /// Generates a value from a normal distribution with the given mean and standard
+    /// deviation, using the Box-Muller transform. Each call draws two values and
+    /// returns a single sample, as caching the spare sample would need extra state.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn gen_normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        mean + std_dev * self.standard_normal()
     }
 
-    #[test]
-    fn clone_rng() {
-        let rng = WyRand::new(Default::default());
+    /// Generates a value from a normal distribution with the given mean and standard
+    /// deviation, truncated to `[lo, hi]` by rejecting samples outside the bounds.
+    ///
+    /// If the interval holds too little of the distribution's probability mass for a
+    /// sample to be accepted within a bounded number of attempts, this falls back to
+    /// a uniform value in `[lo, hi)` rather than looping indefinitely.
+    #[cfg(feature = "std")]
+    pub fn rand_truncated_normal(&mut self, mean: f64, std: f64, lo: f64, hi: f64) -> f64 {
+        const MAX_ATTEMPTS: u32 = 256;
 
-        let mut cloned = rng.clone();
+        debug_assert!(lo <= hi, "the lower bound must not exceed the upper bound");
 
-        // Should be the same internal state after cloning
-        assert_eq!(
-            &rng, &cloned,
-            "the two RNG instances are not the same after cloning"
-        );
+        for _ in 0..MAX_ATTEMPTS {
+            let sample = self.gen_normal(mean, std);
 
-        cloned.rand();
+            if (lo..=hi).contains(&sample) {
+                return sample;
+            }
+        }
 
-        // Should no longer have the same internal state after generating a random number
-        assert_ne!(
-            &rng, &cloned,
-            "the two RNG instances are the same after one was used"
-        );
+        lo + (hi - lo) * self.gen_f64()
     }
 
-    #[cfg(feature = "rand_core")]
-    #[test]
-    fn rand_core_integration() {
-        fn rand_generic<R: RngCore>(mut r: R) -> u32 {
-            r.next_u32()
+    /// Generates a uniformly random instant within `[start, end)`, returning `start`
+    /// if `start` isn't before `end`. Spans too large to count in nanoseconds as a
+    /// [`u64`](prim@u64), roughly 584 years, are sampled at a resolution of seconds.
+    #[cfg(feature = "std")]
+    pub fn gen_system_time(
+        &mut self,
+        start: std::time::SystemTime,
+        end: std::time::SystemTime,
+    ) -> std::time::SystemTime {
+        use std::time::Duration;
+
+        let span = match end.duration_since(start) {
+            Ok(span) if !span.is_zero() => span,
+            _ => return start,
+        };
+
+        let offset = match u64::try_from(span.as_nanos()) {
+            Ok(nanos) => Duration::from_nanos(self.gen_range(..nanos)),
+            Err(_) => Duration::from_secs(self.gen_range(..span.as_secs())),
+        };
+
+        start + offset
+    }
+
+    /// Selects up to `k` items from a stream of `(weight, item)` pairs in a single
+    /// pass, using A-Res weighted reservoir sampling. Items are included with
+    /// probability proportional to their weight, and zero weight items are never
+    /// selected. The selected items are returned in no particular order.
+    #[cfg(feature = "std")]
+    pub fn weighted_reservoir<T>(
+        &mut self,
+        items: impl Iterator<Item = (u32, T)>,
+        k: usize,
+    ) -> Vec<T> {
+        use std::collections::BinaryHeap;
+
+        if k == 0 {
+            return Vec::new();
         }
 
-        fn rand_dyn(r: &mut dyn RngCore) -> u32 {
-            r.next_u32()
+        // Min-heap on the keys, so the weakest item in the reservoir is on top.
+        let mut reservoir: BinaryHeap<ReservoirEntry<T>> = BinaryHeap::with_capacity(k);
+
+        for (weight, item) in items.filter(|&(weight, _)| weight > 0) {
+            // `ln(u) / w` orders items the same as the `u^(1/w)` keys of A-Res, while
+            // `1 - u` lies within `(0, 1]` so the logarithm never sees zero.
+            let key = (1.0 - self.gen_f64()).ln() / f64::from(weight);
+
+            if reservoir.len() < k {
+                reservoir.push(ReservoirEntry { key, item });
+            } else if reservoir.peek().map_or(false, |weakest| key > weakest.key) {
+                reservoir.pop();
+                reservoir.push(ReservoirEntry { key, item });
+            }
         }
 
-        let mut rng = WyRand::from_seed(Default::default());
+        reservoir.into_iter().map(|entry| entry.item).collect()
+    }
 
-        assert_eq!(rand_generic(&mut rng), 2_405_016_974);
-        assert_eq!(rand_dyn(&mut rng), 4_283_336_045);
+    /// Generates a value from the standard normal distribution with the Box-Muller
+    /// transform.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn standard_normal(&mut self) -> f64 {
+        // `1 - u` lies within `(0, 1]`, so the logarithm never sees zero.
+        let radius = (-2.0 * (1.0 - self.gen_f64()).ln()).sqrt();
+        let angle = core::f64::consts::TAU * self.gen_f64();
+
+        radius * angle.cos()
     }
 
-    #[cfg(all(feature = "serde1", feature = "debug"))]
-    #[test]
-    fn serde_tokens() {
-        use serde_test::{assert_tokens, Token};
+    /// Creates a new [`WyRand`] instance seeded from the current system time.
+    ///
+    /// This is a **low entropy** source, only suitable for quick and dirty seeding
+    /// where nothing important depends on the quality of the seed. An internal
+    /// counter is mixed in so that instances created within the same clock tick
+    /// still receive different seeds.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_time() -> Self {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
 
-        let seed = 12345;
-        let rng = WyRand::new(seed);
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed) as u64;
+
+        Self::new(wymix(nanos ^ WY0, count ^ WY1))
+    }
+
+    /// Creates a new [`WyRand`] instance seeded from OS entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS entropy source fails. See [`WyRand::try_from_entropy`] for a
+    /// fallible variant.
+    #[cfg(feature = "entropy")]
+    #[inline]
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        Self::try_from_entropy().expect("Failed to obtain entropy for seeding")
+    }
+
+    /// Creates a new [`WyRand`] instance seeded from OS entropy, returning an error
+    /// if entropy couldn't be obtained.
+    #[cfg(feature = "entropy")]
+    #[inline]
+    pub fn try_from_entropy() -> Result<Self, getrandom::Error> {
+        get_random_u64().map(Self::new)
+    }
+
+    /// Overwrites the PRNG state with a fresh value sourced from OS entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS entropy source fails. See
+    /// [`WyRand::try_reseed_from_entropy`] for a fallible variant.
+    #[cfg(feature = "entropy")]
+    #[inline]
+    pub fn reseed_from_entropy(&mut self) {
+        self.try_reseed_from_entropy()
+            .expect("Failed to obtain entropy for reseeding");
+    }
+
+    /// Overwrites the PRNG state with a fresh value sourced from OS entropy,
+    /// returning an error and leaving the state untouched if entropy couldn't
+    /// be obtained.
+    #[cfg(feature = "entropy")]
+    #[inline]
+    pub fn try_reseed_from_entropy(&mut self) -> Result<(), getrandom::Error> {
+        self.state = get_random_u64()?;
+
+        Ok(())
+    }
+
+    /// Samples an index from the distribution described by `cdf`, a non-decreasing
+    /// cumulative distribution ending at `1.0`. Index `i` is chosen with probability
+    /// `cdf[i] - cdf[i - 1]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cdf` is empty.
+    #[inline]
+    pub fn sample_cdf(&mut self, cdf: &[f64]) -> usize {
+        assert!(!cdf.is_empty(), "cannot sample from an empty CDF");
+        debug_assert!(
+            cdf.windows(2).all(|pair| pair[0] <= pair[1]),
+            "the CDF must be non-decreasing"
+        );
+        debug_assert!(
+            cdf.last().map_or(false, |&last| (last - 1.0).abs() < 1e-9),
+            "the CDF must end at 1.0"
+        );
+
+        let value = self.gen_f64();
+
+        // Clamped to guard against a final value rounded to just below 1.0.
+        cdf.partition_point(|&probability| probability <= value)
+            .min(cdf.len() - 1)
+    }
+
+    /// Returns an endless iterator of random [`u64`](prim@u64) values which borrows the
+    /// generator, so it can be used with the standard iterator combinators.
+    #[inline]
+    pub fn iter(&mut self) -> WyRandIter<'_> {
+        WyRandIter { rng: self }
+    }
+
+    /// Converts the generator into an owned, endless iterator of random [`u64`](prim@u64)
+    /// values. See [`WyRand::iter`] for a borrowing iterator.
+    #[inline]
+    #[must_use]
+    pub fn into_stream(self) -> WyRandStream {
+        WyRandStream { rng: self }
+    }
+
+    /// Returns an endless iterator of independent, unbiased random values from
+    /// within the provided range. The rejection threshold is computed once up front,
+    /// so each value avoids a division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn range_iter(&mut self, range: Range<u64>) -> impl Iterator<Item = u64> + '_ {
+        let uniform = UniformU64::new(range);
+
+        core::iter::repeat_with(move || uniform.sample(self))
+    }
+
+    /// Returns an iterator yielding `count` independent, unbiased random values
+    /// from within the provided range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn range_step_iter(
+        &mut self,
+        range: Range<u64>,
+        count: usize,
+    ) -> impl Iterator<Item = u64> + '_ {
+        self.range_iter(range).take(count)
+    }
+
+    /// Returns the minimum of `draws` uniform samples from the range, skewing the
+    /// result toward low values. Over a range of `n` values, the result follows the
+    /// first order statistic, with `P(X >= x) = ((n - x) / n)^draws` relative to the
+    /// range start, so each additional draw pulls the expected value further down.
+    /// A `draws` of `0` is treated as `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn sample_biased_min(&mut self, range: Range<u64>, draws: u32) -> u64 {
+        let uniform = UniformU64::new(range);
+        let first = uniform.sample(self);
+
+        (1..draws).fold(first, |min, _| min.min(uniform.sample(self)))
+    }
+
+    /// Returns the maximum of `draws` uniform samples from the range, skewing the
+    /// result toward high values. This mirrors [`WyRand::sample_biased_min`],
+    /// following the last order statistic instead. A `draws` of `0` is treated as `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn sample_biased_max(&mut self, range: Range<u64>, draws: u32) -> u64 {
+        let uniform = UniformU64::new(range);
+        let first = uniform.sample(self);
+
+        (1..draws).fold(first, |max, _| max.max(uniform.sample(self)))
+    }
 
-        assert_tokens(
-            &rng,
-            &[
-                Token::Struct {
-                    name: "WyRand",
-                    len: 1,
+    /// Returns an iterator yielding `k` distinct values from `0..n` in random order,
+    /// clamping `k` to `n`. The values come from passing a counter through a randomly
+    /// keyed Feistel network over the smallest even bit width holding `n`, skipping
+    /// any outputs outside the range, so no allocation is needed and memory use is
+    /// constant. The rounds approximate a uniformly random permutation rather than
+    /// being exactly one, so the selected subsets and their order are close to, but
+    /// not strictly, uniform.
+    #[inline]
+    pub fn sample_range_no_replace(&mut self, n: u64, k: u64) -> impl Iterator<Item = u64> {
+        // Each half of the Feistel network covers half the bits needed to hold `n`,
+        // rounded up, so the permuted domain is at most four times larger than `n`.
+        let half_bits = (u64::BITS - n.saturating_sub(1).leading_zeros() + 1) / 2;
+        let half_mask = (1u64 << half_bits) - 1;
+        let keys: [u64; 8] = core::array::from_fn(|_| self.rand());
+        let permute = move |value: u64| {
+            let (left, right) = keys.iter().fold(
+                (value >> half_bits, value & half_mask),
+                |(left, right), &key| {
+                    let mixed = (right ^ key).wrapping_add(WY0);
+
+                    (right, left ^ (wymix(mixed, mixed ^ WY1) & half_mask))
                 },
-                Token::BorrowedStr("state"),
-                Token::U64(seed),
-                Token::StructEnd,
-            ],
+            );
+
+            (left << half_bits) | right
+        };
+        let mut counter = 0u64;
+
+        (0..k.min(n)).map(move |_| loop {
+            let value = permute(counter);
+            counter = counter.wrapping_add(1);
+
+            if value < n {
+                break value;
+            }
+        })
+    }
+
+    /// Generates a uniform value from the range that isn't present in `exclude`,
+    /// which must be sorted in ascending order without duplicates. Returns [`None`]
+    /// if every value in the range is excluded, or if the range is empty.
+    pub fn gen_range_excluding(&mut self, range: Range<u64>, exclude: &[u64]) -> Option<u64> {
+        debug_assert!(
+            exclude.windows(2).all(|pair| pair[0] < pair[1]),
+            "the excluded values must be sorted and distinct"
         );
+
+        let span = range.end.checked_sub(range.start)?;
+        let excluded = &exclude[exclude.partition_point(|&value| value < range.start)
+            ..exclude.partition_point(|&value| value < range.end)];
+        let available = span
+            .checked_sub(excluded.len() as u64)
+            .filter(|&available| available > 0)?;
+
+        // Pick the nth allowed value, then step over every excluded value up to it.
+        let mut value = range.start + self.bounded(available);
+
+        for &skipped in excluded {
+            if skipped > value {
+                break;
+            }
+
+            value += 1;
+        }
+
+        Some(value)
+    }
+
+    /// Returns the indices of `weights` in a random order, where each position is
+    /// filled by picking from the remaining indices with probability proportional
+    /// to their weight. Indices with a weight of zero are placed last, in ascending
+    /// order.
+    ///
+    /// Only integer arithmetic is used, so the ordering for a given seed is
+    /// identical on every platform. Each pick walks the remaining indices, making
+    /// this `O(n^2)` over the number of weighted indices.
+    #[cfg(feature = "alloc")]
+    pub fn weighted_order_int(&mut self, weights: &[u32]) -> Vec<usize> {
+        let mut remaining: Vec<usize> = (0..weights.len()).filter(|&i| weights[i] > 0).collect();
+        let mut total: u64 = remaining.iter().map(|&i| u64::from(weights[i])).sum();
+        let mut order = Vec::with_capacity(weights.len());
+
+        while total > 0 {
+            let mut target = self.gen_range(0..total);
+            let position = remaining
+                .iter()
+                .position(|&i| {
+                    let weight = u64::from(weights[i]);
+
+                    if target < weight {
+                        true
+                    } else {
+                        target -= weight;
+                        false
+                    }
+                })
+                .expect("target should fall within the total weight");
+            let index = remaining.remove(position);
+
+            total -= u64::from(weights[index]);
+            order.push(index);
+        }
+
+        order.extend((0..weights.len()).filter(|&i| weights[i] == 0));
+
+        order
+    }
+
+    /// Returns a uniformly random permutation of `0..n` as a materialized [`Vec`].
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn random_bijection(&mut self, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..n).collect();
+
+        self.shuffle(&mut indices);
+
+        indices
+    }
+
+    /// Returns the permutation of `0..n` that a generator seeded with `seed` would
+    /// produce via [`WyRand::random_bijection`], so an ordering can be reproduced
+    /// from just a seed and length without keeping a generator around.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn shuffled_order(seed: u64, n: usize) -> Vec<usize> {
+        Self::new(seed).random_bijection(n)
+    }
+
+    /// Returns a packed bitset of `bits` length, where each bit is set with
+    /// probability `density`. Bit `i` is stored in word `i / 64` at position
+    /// `i % 64`, and any unused bits of the final word are left unset.
+    ///
+    /// `density` is used at 32-bit precision, and each word combines one generated
+    /// value per significant bit of it. So `0.5` takes a single value per word, `0.25`
+    /// or `0.75` take two, and no density takes more than 32. A `density` of `0.0` or
+    /// less (or NaN) and of `1.0` or more don't generate any values.
+    #[cfg(feature = "alloc")]
+    pub fn random_bitset(&mut self, bits: usize, density: f64) -> Vec<u64> {
+        let words = (bits + 63) / 64;
+
+        let mut bitset: Vec<u64> = if density <= 0.0 || density.is_nan() {
+            alloc::vec![0; words]
+        } else if density >= 1.0 {
+            alloc::vec![u64::MAX; words]
+        } else {
+            let threshold = (density * (1u64 << 32) as f64) as u32;
+
+            // Walking the bits of the threshold from least to most significant, ORing
+            // in a value for a set bit and ANDing one for an unset bit turns a bit's
+            // probability `p` into `(b + p) / 2`, ending at `threshold / 2^32`.
+            (0..words)
+                .map(|_| {
+                    (threshold.trailing_zeros()..32).fold(0, |word, bit| {
+                        if (threshold >> bit) & 1 == 1 {
+                            word | self.rand()
+                        } else {
+                            word & self.rand()
+                        }
+                    })
+                })
+                .collect()
+        };
+
+        if let Some(last) = bitset.last_mut() {
+            let used = bits % 64;
+
+            if used != 0 {
+                *last &= (1 << used) - 1;
+            }
+        }
+
+        bitset
+    }
+
+    /// Returns a string of random Unicode scalar values, stopping once the next
+    /// generated character would exceed `max_bytes` of UTF-8. The encoded width of
+    /// each character is chosen uniformly, so multibyte characters are as common
+    /// as ASCII ones.
+    #[cfg(feature = "alloc")]
+    pub fn gen_utf8(&mut self, max_bytes: usize) -> String {
+        let mut output = String::new();
+
+        loop {
+            let scalar = match self.index(4) {
+                0 => self.bounded(0x80),
+                1 => 0x80 + self.bounded(0x800 - 0x80),
+                2 => {
+                    let scalar = 0x800 + self.bounded(0x1_0000 - 0x800 - 0x800);
+
+                    // Skip over the surrogate range, which aren't valid scalar values.
+                    if scalar >= 0xD800 {
+                        scalar + 0x800
+                    } else {
+                        scalar
+                    }
+                }
+                _ => 0x1_0000 + self.bounded(0x11_0000 - 0x1_0000),
+            };
+            let character = char::from_u32(scalar as u32).expect("valid unicode scalar value");
+
+            if output.len() + character.len_utf8() > max_bytes {
+                return output;
+            }
+
+            output.push(character);
+        }
+    }
+
+    /// Returns a random byte vector for property testing, with a length within
+    /// `0..=max_len`. Half of the time the length is picked from edge cases that
+    /// commonly sit on branch boundaries (0, 1, 16, 47, 48, 49 and `max_len`
+    /// itself), and otherwise uniformly.
+    #[cfg(feature = "alloc")]
+    pub fn proptest_bytes(&mut self, max_len: usize) -> Vec<u8> {
+        const EDGE_LENGTHS: [usize; 6] = [0, 1, 16, 47, 48, 49];
+
+        let edges = EDGE_LENGTHS.partition_point(|&len| len <= max_len);
+
+        let len = if self.random::<bool>() {
+            // `edges` indexes past the in-bounds edge lengths, standing in for `max_len`.
+            match self.index(edges + 1) {
+                i if i == edges => max_len,
+                i => EDGE_LENGTHS[i],
+            }
+        } else {
+            self.gen_range(0..=max_len as u64) as usize
+        };
+
+        let mut bytes = alloc::vec![0u8; len];
+
+        for chunk in bytes.chunks_mut(core::mem::size_of::<u64>()) {
+            chunk.copy_from_slice(&self.rand().to_le_bytes()[..chunk.len()]);
+        }
+
+        bytes
+    }
+
+    /// Returns `n` fair coin flips, consuming each bit of a generated value rather
+    /// than generating a value per flip.
+    #[cfg(feature = "alloc")]
+    pub fn gen_bools(&mut self, n: usize) -> Vec<bool> {
+        let mut bools = Vec::with_capacity(n);
+        let mut bits = 0;
+
+        for i in 0..n {
+            if i % 64 == 0 {
+                bits = self.rand();
+            }
+
+            bools.push(bits & 1 == 1);
+            bits >>= 1;
+        }
+
+        bools
+    }
+
+    /// Returns `n` non-negative values summing to `1.0`, distributed uniformly over
+    /// the probability simplex. This uses the spacings between `n - 1` sorted
+    /// uniform values, so it's suited to generating random probability distributions.
+    #[cfg(feature = "alloc")]
+    pub fn gen_simplex(&mut self, n: usize) -> Vec<f64> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut cuts: Vec<f64> = (1..n).map(|_| self.gen_f64()).collect();
+
+        cuts.sort_unstable_by(|a, b| a.partial_cmp(b).expect("generated floats are never NaN"));
+        cuts.push(1.0);
+
+        let mut previous = 0.0;
+
+        for cut in &mut cuts {
+            let spacing = *cut - previous;
+
+            previous = *cut;
+            *cut = spacing;
+        }
+
+        cuts
+    }
+
+    /// Returns `k` distinct, randomly chosen references to elements of the slice,
+    /// in random order. If `k` is larger than the slice, all elements are returned.
+    #[cfg(feature = "alloc")]
+    pub fn choose_multiple<'a, T>(&mut self, slice: &'a [T], k: usize) -> Vec<&'a T> {
+        let k = k.min(slice.len());
+        let mut indices: Vec<usize> = (0..slice.len()).collect();
+
+        // Partial Fisher-Yates, only shuffling the first `k` positions.
+        for i in 0..k {
+            let j = i + self.index(slice.len() - i);
+            indices.swap(i, j);
+        }
+
+        indices[..k].iter().map(|&index| &slice[index]).collect()
+    }
+
+    /// Returns `n` stratified samples in `[0, 1)`, where sample `i` is drawn
+    /// uniformly from the stratum `[i/n, (i+1)/n)`. This gives better coverage of
+    /// the unit interval than `n` plain uniform draws. The samples are returned in
+    /// stratum order, so shuffle them if the order matters.
+    #[cfg(feature = "alloc")]
+    pub fn stratified(&mut self, n: usize) -> Vec<f64> {
+        let strata = n as f64;
+
+        (0..n)
+            .map(|i| {
+                let upper = (i + 1) as f64 / strata;
+                let sample = (i as f64 + self.gen_f64()) / strata;
+
+                // Rounding can push a sample onto the upper bound of its stratum,
+                // so clamp it to the largest value below the bound.
+                if sample < upper {
+                    sample
+                } else {
+                    f64::from_bits(upper.to_bits() - 1)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a random variant from a slice listing all the variants of a type.
+    /// For fieldless enums, [`random_enum!`] can generate this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `variants` is empty.
+    #[inline]
+    pub fn choose_variant<T: Copy>(&mut self, variants: &[T]) -> T {
+        variants[self.index(variants.len())]
+    }
+
+    /// Returns a random element of the slice along with its index, or [`None`] if
+    /// the slice is empty.
+    #[inline]
+    pub fn choose_with_index<'a, T>(&mut self, slice: &'a [T]) -> Option<(usize, &'a T)> {
+        if slice.is_empty() {
+            None
+        } else {
+            let index = self.index(slice.len());
+
+            Some((index, &slice[index]))
+        }
+    }
+
+    /// Returns a random element of the slice by value, or [`None`] if the slice
+    /// is empty.
+    #[inline]
+    pub fn choose_from<T: Copy>(&mut self, values: &[T]) -> Option<T> {
+        if values.is_empty() {
+            None
+        } else {
+            Some(values[self.index(values.len())])
+        }
+    }
+
+    /// Returns a reference to a random element of the slice, or [`None`] if the
+    /// slice is empty.
+    #[inline]
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            None
+        } else {
+            Some(&slice[self.index(slice.len())])
+        }
+    }
+
+    /// Returns a mutable reference to a random element of the slice, or [`None`]
+    /// if the slice is empty.
+    #[inline]
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            None
+        } else {
+            let index = self.index(slice.len());
+
+            Some(&mut slice[index])
+        }
+    }
+
+    /// Returns a random element of the slice, chosen with probability proportional
+    /// to its weight, or [`None`] if the slice is empty or every weight is zero.
+    /// Elements with a weight of zero are never chosen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of all the weights overflows a [`u64`](prim@u64).
+    pub fn choose_weighted<'a, T>(
+        &mut self,
+        items: &'a [T],
+        weight: impl Fn(&T) -> u64,
+    ) -> Option<&'a T> {
+        let total = items.iter().fold(0u64, |total, item| {
+            total
+                .checked_add(weight(item))
+                .expect("total weight should not overflow a u64")
+        });
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut target = self.gen_range(0..total);
+
+        items.iter().find(|item| {
+            let weight = weight(item);
+
+            if target < weight {
+                true
+            } else {
+                target -= weight;
+                false
+            }
+        })
+    }
+
+    /// Shuffles the slice in place with the Fisher-Yates algorithm, drawing
+    /// unbiased indices so every permutation is equally likely. Empty and
+    /// single-element slices are left untouched.
+    #[inline]
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.index(i + 1);
+            slice.swap(i, j);
+        }
+    }
+
+    /// Generates an unbiased random index into a container of length `len`. The
+    /// index is always drawn as a [`u64`](prim@u64), so the same seed yields the same
+    /// indices regardless of the platform's [`usize`](prim@usize) width.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    #[inline]
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot generate an index for an empty container");
+
+        self.bounded(len as u64) as usize
+    }
+
+    /// Generates an unbiased random value within the provided range, using Lemire's
+    /// multiply-and-shift method so there is no modulo bias. Accepts both half-open
+    /// (`a..b`) and inclusive (`a..=b`) ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn gen_range(&mut self, range: impl RangeBounds<u64>) -> u64 {
+        let low = match range.start_bound() {
+            Bound::Included(&low) => low,
+            Bound::Excluded(&low) => low
+                .checked_add(1)
+                .expect("cannot sample from an empty range"),
+            Bound::Unbounded => 0,
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&high) => high,
+            Bound::Excluded(&high) => high
+                .checked_sub(1)
+                .expect("cannot sample from an empty range"),
+            Bound::Unbounded => u64::MAX,
+        };
+
+        assert!(low <= high, "cannot sample from an empty range");
+
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.bounded(span),
+            None => self.rand(),
+        }
+    }
+
+    /// Generates an unbiased random [`i64`] value within the provided range, sampling
+    /// an offset over the unsigned width of the range and adding it to the start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    #[inline]
+    pub fn gen_range_i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "cannot sample from an empty range");
+
+        // Wrapping keeps the width correct even for spans wider than `i64::MAX`.
+        let width = range.end.wrapping_sub(range.start) as u64;
+
+        range.start.wrapping_add(self.bounded(width) as i64)
+    }
+
+    /// Generates an unbiased value in `0..bound` using Lemire's nearly
+    /// divisionless method. `bound` must be non-zero.
+    #[inline]
+    fn bounded(&mut self, bound: u64) -> u64 {
+        self.bounded_with(bound, |bound| bound.wrapping_neg() % bound)
+    }
+
+    /// Lemire's rejection loop behind [`WyRand::bounded`], which only computes the
+    /// rejection threshold with `threshold` when a draw lands close enough to need
+    /// it. This lets callers supply a cached threshold instead of dividing.
+    #[inline]
+    fn bounded_with(&mut self, bound: u64, threshold: impl FnOnce(u64) -> u64) -> u64 {
+        let mut m = u128::from(self.rand()) * u128::from(bound);
+
+        if (m as u64) < bound {
+            let threshold = threshold(bound);
+
+            while (m as u64) < threshold {
+                m = u128::from(self.rand()) * u128::from(bound);
+            }
+        }
+
+        (m >> 64) as u64
+    }
+}
+
+/// A [`WyRand`] tagged with a marker type, so generators used for different
+/// purposes can't be accidentally swapped. It dereferences to [`WyRand`], sharing
+/// its implementation and producing the same sequence for the same seed.
+///
+/// ```rust
+/// use wyrand::WyRandTyped;
+///
+/// struct Loot;
+///
+/// fn roll_loot(rng: &mut WyRandTyped<Loot>) -> u64 {
+///     rng.rand()
+/// }
+///
+/// let mut rng = WyRandTyped::<Loot>::new(Default::default());
+///
+/// let value = roll_loot(&mut rng);
+/// ```
+///
+/// Generators with different tags are distinct types:
+///
+/// ```compile_fail
+/// use wyrand::WyRandTyped;
+///
+/// struct Damage;
+/// struct Loot;
+///
+/// fn roll_loot(rng: &mut WyRandTyped<Loot>) -> u64 {
+///     rng.rand()
+/// }
+///
+/// let mut rng = WyRandTyped::<Damage>::new(Default::default());
+///
+/// let value = roll_loot(&mut rng);
+/// ```
+#[repr(transparent)]
+pub struct WyRandTyped<Tag> {
+    rng: WyRand,
+    tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag> WyRandTyped<Tag> {
+    /// Creates a new [`WyRandTyped`] instance with the provided seed.
+    #[inline]
+    #[must_use]
+    pub fn new(state: u64) -> Self {
+        Self::from(WyRand::new(state))
+    }
+
+    /// Unwraps the tagged generator, returning the inner [`WyRand`].
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> WyRand {
+        self.rng
+    }
+}
+
+impl<Tag> From<WyRand> for WyRandTyped<Tag> {
+    #[inline]
+    fn from(rng: WyRand) -> Self {
+        Self {
+            rng,
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<Tag> Deref for WyRandTyped<Tag> {
+    type Target = WyRand;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.rng
+    }
+}
+
+impl<Tag> DerefMut for WyRandTyped<Tag> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rng
+    }
+}
+
+impl<Tag> Clone for WyRandTyped<Tag> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::from(self.rng.clone())
+    }
+}
+
+impl<Tag> PartialEq for WyRandTyped<Tag> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.rng == other.rng
+    }
+}
+
+impl<Tag> Eq for WyRandTyped<Tag> {}
+
+#[cfg(feature = "debug")]
+impl<Tag> Debug for WyRandTyped<Tag> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WyRandTyped").finish()
+    }
+}
+
+/// A [`WyRand`] wrapper that buffers the bits of each generated value, so a single
+/// draw yields two [`u32`], four [`u16`] or eight [`u8`] values before refilling.
+///
+/// Values are taken from the most significant bits first, so the first
+/// [`BufferedWyRand::gen_u32`] after a refill returns the high half of the generated
+/// [`u64`](prim@u64) and the second returns the low half. If fewer bits remain than
+/// requested, the leftover bits are discarded and a fresh value is generated. This
+/// lives in a separate type so that [`WyRand`] stays a transparent wrapper over
+/// its [`u64`](prim@u64) state.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BufferedWyRand {
+    rng: WyRand,
+    buffer: u64,
+    remaining: u32,
+}
+
+impl BufferedWyRand {
+    /// Creates a new [`BufferedWyRand`] instance, starting with an empty buffer.
+    #[inline]
+    #[must_use]
+    pub fn new(rng: WyRand) -> Self {
+        Self {
+            rng,
+            buffer: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Unwraps the buffered generator, discarding any buffered bits and returning
+    /// the inner [`WyRand`].
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> WyRand {
+        self.rng
+    }
+
+    /// Generates a random [`u32`] value from the buffered bits.
+    #[inline]
+    pub fn gen_u32(&mut self) -> u32 {
+        self.take_bits(32) as u32
+    }
+
+    /// Generates a random [`u16`] value from the buffered bits.
+    #[inline]
+    pub fn gen_u16(&mut self) -> u16 {
+        self.take_bits(16) as u16
+    }
+
+    /// Generates a random [`u8`] value from the buffered bits.
+    #[inline]
+    pub fn gen_u8(&mut self) -> u8 {
+        self.take_bits(8) as u8
+    }
+
+    #[inline]
+    fn take_bits(&mut self, bits: u32) -> u64 {
+        if self.remaining < bits {
+            self.buffer = self.rng.rand();
+            self.remaining = 64;
+        }
+
+        let value = self.buffer >> (64 - bits);
+
+        self.buffer <<= bits;
+        self.remaining -= bits;
+
+        value
+    }
+}
+
+#[cfg(feature = "debug")]
+impl Debug for BufferedWyRand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BufferedWyRand").finish()
+    }
+}
+
+/// An item held in the reservoir of [`WyRand::weighted_reservoir`], ordered in
+/// reverse by its key so a [`std::collections::BinaryHeap`] keeps the smallest
+/// key on top.
+#[cfg(feature = "std")]
+struct ReservoirEntry<T> {
+    key: f64,
+    item: T,
+}
+
+#[cfg(feature = "std")]
+impl<T> PartialEq for ReservoirEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Eq for ReservoirEntry<T> {}
+
+#[cfg(feature = "std")]
+impl<T> PartialOrd for ReservoirEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Ord for ReservoirEntry<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+/// An endless iterator of random [`u64`](prim@u64) values, created by [`WyRand::iter`].
+///
+/// [`Iterator`] is implemented on this borrowing wrapper rather than on [`WyRand`]
+/// itself, as iterator adapters take `self` by value and would otherwise consume
+/// the generator.
+pub struct WyRandIter<'a> {
+    rng: &'a mut WyRand,
+}
+
+impl Iterator for WyRandIter<'_> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.rng.rand())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// An owned, endless iterator of random [`u64`](prim@u64) values, created by
+/// [`WyRand::into_stream`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[repr(transparent)]
+pub struct WyRandStream {
+    rng: WyRand,
+}
+
+impl WyRandStream {
+    /// Unwraps the stream, returning the inner [`WyRand`] at its current state.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> WyRand {
+        self.rng
+    }
+}
+
+impl Iterator for WyRandStream {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.rng.rand())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Samples unbiased values from a range of [`u64`](prim@u64) values using Lemire's
+/// multiply-and-shift method, with the rejection threshold computed once
+/// up front so repeated sampling avoids the division.
+#[derive(Clone, Copy)]
+struct UniformU64 {
+    low: u64,
+    /// Number of values in the range, which is never zero.
+    span: u64,
+    threshold: u64,
+}
+
+impl UniformU64 {
+    #[inline]
+    fn new(range: Range<u64>) -> Self {
+        assert!(range.start < range.end, "cannot sample from an empty range");
+
+        let span = range.end - range.start;
+
+        Self {
+            low: range.start,
+            span,
+            threshold: span.wrapping_neg() % span,
+        }
+    }
+
+    #[inline]
+    fn sample(&self, rng: &mut WyRand) -> u64 {
+        self.low + rng.bounded_with(self.span, |_| self.threshold)
+    }
+}
+
+/// Types that can be randomly generated from a [`WyRand`] instance.
+///
+/// Integers are generated across their full range, taking the high bits of
+/// generated values for types narrower than 64 bits. Floats are generated in
+/// the half-open range `[0, 1)`. Tuples generate each component in order.
+pub trait Random {
+    /// Generates a random value with the provided [`WyRand`] instance.
+    fn random(rng: &mut WyRand) -> Self;
+}
+
+macro_rules! impl_random_for_int {
+    ($($ty:ty),+) => {
+        $(
+            impl Random for $ty {
+                #[inline]
+                fn random(rng: &mut WyRand) -> Self {
+                    (rng.rand() >> (64 - <$ty>::BITS)) as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_random_for_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Random for u128 {
+    #[inline]
+    fn random(rng: &mut WyRand) -> Self {
+        (u128::from(rng.rand()) << 64) | u128::from(rng.rand())
+    }
+}
+
+impl Random for i128 {
+    #[inline]
+    fn random(rng: &mut WyRand) -> Self {
+        u128::random(rng) as i128
+    }
+}
+
+impl Random for bool {
+    #[inline]
+    fn random(rng: &mut WyRand) -> Self {
+        rng.rand() >> 63 == 1
+    }
+}
+
+impl Random for f32 {
+    #[inline]
+    fn random(rng: &mut WyRand) -> Self {
+        rng.gen_f32()
+    }
+}
+
+impl Random for f64 {
+    #[inline]
+    fn random(rng: &mut WyRand) -> Self {
+        rng.gen_f64()
+    }
+}
+
+macro_rules! impl_random_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Random),+> Random for ($($name,)+) {
+            #[inline]
+            fn random(rng: &mut WyRand) -> Self {
+                ($($name::random(rng),)+)
+            }
+        }
+    };
+}
+
+impl_random_for_tuple!(A);
+impl_random_for_tuple!(A, B);
+impl_random_for_tuple!(A, B, C);
+impl_random_for_tuple!(A, B, C, D);
+impl_random_for_tuple!(A, B, C, D, E);
+impl_random_for_tuple!(A, B, C, D, E, F);
+impl_random_for_tuple!(A, B, C, D, E, F, G);
+impl_random_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// Implements a `gen_random(&mut WyRand) -> Self` associated function for a
+/// fieldless enum, by listing its variants. The enum must implement [`Copy`].
+///
+/// ```rust
+/// use wyrand::{random_enum, WyRand};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// enum Direction {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// random_enum!(Direction { North, East, South, West });
+///
+/// let mut rng = WyRand::new(Default::default());
+///
+/// let direction = Direction::gen_random(&mut rng);
+/// ```
+#[macro_export]
+macro_rules! random_enum {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl $name {
+            /// Generates a random variant of this enum.
+            #[inline]
+            pub fn gen_random(rng: &mut $crate::WyRand) -> Self {
+                rng.choose_variant(&[$($name::$variant),+])
+            }
+        }
+    };
+}
+
+/// Implements [`Random`] for a struct with named fields, by listing its fields.
+/// Each field is generated with its own [`Random`] implementation, in the order
+/// they are listed.
+///
+/// ```rust
+/// use wyrand::{random_fields, Random, WyRand};
+///
+/// struct Particle {
+///     id: u32,
+///     x: f32,
+///     y: f32,
+///     alive: bool,
+/// }
+///
+/// random_fields!(Particle { id, x, y, alive });
+///
+/// let mut rng = WyRand::new(Default::default());
+///
+/// let particle: Particle = rng.random();
+/// ```
+#[macro_export]
+macro_rules! random_fields {
+    ($name:ident { $($field:ident),+ $(,)? }) => {
+        impl $crate::Random for $name {
+            #[inline]
+            fn random(rng: &mut $crate::WyRand) -> Self {
+                Self {
+                    $($field: $crate::Random::random(rng)),+
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "debug")]
+impl Debug for WyRand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WyRand").finish()
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl RngCore for WyRand {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(core::mem::size_of::<u64>());
+
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.rand().to_le_bytes());
+        }
+
+        let remainder = chunks.into_remainder();
+
+        if !remainder.is_empty() {
+            let bytes = self.rand().to_le_bytes();
+            remainder.copy_from_slice(&bytes[..remainder.len()]);
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl SeedableRng for WyRand {
+    type Seed = [u8; core::mem::size_of::<u64>()];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_ne_bytes(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::format;
+
+    use super::*;
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn no_leaking_debug() {
+        let rng = WyRand::new(Default::default());
+
+        assert_eq!(
+            format!("{rng:?}"),
+            "WyRand",
+            "Debug should not be leaking internal state"
+        );
+    }
+
+    #[test]
+    fn default_seeded_outputs() {
+        let mut rng = WyRand::default_seeded();
+
+        assert_eq!(rng.rand(), 1_850_025_804_378_807_155);
+        assert_eq!(rng.rand(), 5_967_786_889_647_558_813);
+        assert_eq!(rng.rand(), 12_494_103_359_051_842_108);
+    }
+
+    #[test]
+    fn checkpoint_and_restore_state() {
+        let mut rng = WyRand::new(Default::default());
+
+        rng.discard(10);
+
+        let checkpoint = rng.current_state();
+        let expected: [u64; 4] = [rng.rand(), rng.rand(), rng.rand(), rng.rand()];
+
+        rng.reseed(checkpoint);
+
+        assert_eq!(
+            [rng.rand(), rng.rand(), rng.rand(), rng.rand()],
+            expected,
+            "restoring a checkpoint should reproduce the stream"
+        );
+        assert_eq!(
+            WyRand::new(checkpoint).rand(),
+            expected[0],
+            "a checkpoint should be usable as a seed"
+        );
+    }
+
+    #[test]
+    fn discard_jumps_ahead() {
+        for k in [0, 1, 2, 100, 12_345] {
+            let mut stepped = WyRand::new(42);
+            let mut jumped = WyRand::new(42);
+
+            for _ in 0..k {
+                stepped.rand();
+            }
+
+            jumped.discard(k);
+
+            assert_eq!(
+                jumped.rand(),
+                stepped.rand(),
+                "discarding {k} draws should match generating them"
+            );
+        }
+    }
+
+    #[test]
+    fn fill_u64_matches_rand() {
+        for len in [0, 1, 7, 64] {
+            let mut rng = WyRand::new(42);
+            let mut other = WyRand::new(42);
+
+            let mut values = [0u64; 64];
+            rng.fill_u64(&mut values[..len]);
+
+            for &value in &values[..len] {
+                assert_eq!(value, other.rand(), "fill should match element-wise draws");
+            }
+
+            assert_eq!(rng, other, "fill should advance the state once per element");
+        }
+    }
+
+    #[test]
+    fn fork_child_streams() {
+        let mut parent = WyRand::new(42);
+        let original = parent.clone();
+
+        let mut first = parent.fork();
+        let mut second = parent.fork();
+
+        assert_ne!(parent, original, "forking should advance the parent");
+        assert!(
+            first.iter().take(10).ne(second.iter().take(10)),
+            "two forks should produce different sequences"
+        );
+
+        let mut parent = WyRand::new(42);
+
+        assert!(
+            parent
+                .fork()
+                .iter()
+                .take(10)
+                .eq(WyRand::new(42).fork().iter().take(10)),
+            "forking should be reproducible for a fixed root seed"
+        );
+    }
+
+    #[test]
+    fn clone_rng() {
+        let rng = WyRand::new(Default::default());
+
+        let mut cloned = rng.clone();
+
+        // Should be the same internal state after cloning
+        assert_eq!(
+            &rng, &cloned,
+            "the two RNG instances are not the same after cloning"
+        );
+
+        cloned.rand();
+
+        // Should no longer have the same internal state after generating a random number
+        assert_ne!(
+            &rng, &cloned,
+            "the two RNG instances are the same after one was used"
+        );
+    }
+
+    #[test]
+    fn typed_generators() {
+        struct Damage;
+        struct Loot;
+
+        fn roll_damage(rng: &mut WyRandTyped<Damage>) -> u64 {
+            rng.rand()
+        }
+
+        fn roll_loot(rng: &mut WyRandTyped<Loot>) -> u64 {
+            rng.rand()
+        }
+
+        let mut damage = WyRandTyped::<Damage>::new(42);
+        let mut loot = WyRandTyped::<Loot>::new(42);
+        let mut untyped = WyRand::new(42);
+
+        let expected = untyped.rand();
+
+        assert_eq!(roll_damage(&mut damage), expected);
+        assert_eq!(roll_loot(&mut loot), expected);
+        assert_eq!(damage.into_inner(), untyped);
+    }
+
+    #[test]
+    fn buffered_small_values() {
+        let mut rng = WyRand::new(42);
+        let first = rng.rand();
+        let second = rng.rand();
+        let third = rng.rand();
+
+        let mut buffered = BufferedWyRand::new(WyRand::new(42));
+
+        assert_eq!(buffered.gen_u32(), (first >> 32) as u32);
+        assert_eq!(buffered.gen_u32(), first as u32);
+
+        for i in 0..4 {
+            assert_eq!(buffered.gen_u16(), (second >> (48 - i * 16)) as u16);
+        }
+
+        // Leftover bits are discarded when too few remain
+        assert_eq!(buffered.gen_u8(), (third >> 56) as u8);
+        assert_eq!(buffered.gen_u16(), (third >> 40) as u16);
+        assert_eq!(buffered.gen_u32(), (third >> 8) as u32);
+        assert_eq!(buffered.gen_u32(), (rng.rand() >> 32) as u32);
+
+        assert_eq!(buffered.into_inner(), rng);
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn rand_core_integration() {
+        fn rand_generic<R: RngCore>(mut r: R) -> u32 {
+            r.next_u32()
+        }
+
+        fn rand_dyn(r: &mut dyn RngCore) -> u32 {
+            r.next_u32()
+        }
+
+        let mut rng = WyRand::from_seed(Default::default());
+
+        assert_eq!(rand_generic(&mut rng), 2_405_016_974);
+        assert_eq!(rand_dyn(&mut rng), 4_283_336_045);
+    }
+
+    #[test]
+    fn range_iter_reproducible() {
+        let mut rng = WyRand::new(42);
+
+        assert!(
+            rng.range_iter(100..200)
+                .take(1000)
+                .all(|value| (100..200).contains(&value)),
+            "all values should be within the provided range"
+        );
+
+        let first: alloc::vec::Vec<u64> = WyRand::new(42).range_iter(0..1000).take(5).collect();
+        let second: alloc::vec::Vec<u64> = WyRand::new(42).range_iter(0..1000).take(5).collect();
+
+        assert_eq!(first, second, "the same seed should yield the same values");
+    }
+
+    #[test]
+    fn range_step_iter_samples() {
+        let mut rng = WyRand::new(Default::default());
+
+        let values: alloc::vec::Vec<u64> = rng.range_step_iter(10..20, 100).collect();
+
+        assert_eq!(values.len(), 100, "should yield exactly `count` values");
+        assert!(
+            values.iter().all(|value| (10..20).contains(value)),
+            "all values should be within the provided range"
+        );
+
+        let mut rng = WyRand::new(Default::default());
+
+        assert!(
+            rng.range_step_iter(10..20, 100).eq(values.iter().copied()),
+            "the same seed should yield the same sequence of values"
+        );
+    }
+
+    #[test]
+    fn gen_range_uniform() {
+        let mut rng = WyRand::new(Default::default());
+        let mut counts = [0u32; 6];
+
+        for _ in 0..60_000 {
+            let value = rng.gen_range(10..16);
+
+            assert!((10..16).contains(&value), "{value} is out of range");
+            counts[(value - 10) as usize] += 1;
+        }
+
+        assert!(
+            counts.iter().all(|&count| (9_500..10_500).contains(&count)),
+            "values are not uniformly distributed: {counts:?}"
+        );
+
+        for _ in 0..100 {
+            assert!((1..=3).contains(&rng.gen_range(1..=3)));
+            assert!(rng.gen_range(u64::MAX - 1..) >= u64::MAX - 1);
+        }
+
+        assert_eq!(rng.gen_range(7..=7), 7);
+        assert_eq!(
+            WyRand::new(Default::default()).gen_range(..),
+            WyRand::new(Default::default()).rand(),
+            "the full range should map directly onto generated values"
+        );
+    }
+
+    #[test]
+    fn gen_range_i64_signed_ranges() {
+        let mut rng = WyRand::new(Default::default());
+        let mut seen = [false; 7];
+
+        for _ in 0..1000 {
+            let value = rng.gen_range_i64(-3..4);
+
+            assert!((-3..4).contains(&value), "{value} is out of range");
+            seen[(value + 3) as usize] = true;
+        }
+
+        assert_eq!(seen, [true; 7], "every value straddling zero should appear");
+
+        for _ in 0..1000 {
+            let value = rng.gen_range_i64(-100..-90);
+
+            assert!((-100..-90).contains(&value), "{value} is out of range");
+        }
+
+        let mut signs = [false; 2];
+
+        for _ in 0..100 {
+            let value = rng.gen_range_i64(i64::MIN..i64::MAX);
+
+            assert!(value < i64::MAX, "{value} is out of range");
+            signs[usize::from(value < 0)] = true;
+        }
+
+        assert_eq!(signs, [true; 2], "the full range should cover both signs");
+    }
+
+    #[test]
+    fn index_within_bounds() {
+        let mut rng = WyRand::new(Default::default());
+
+        for len in [1, 2, 3, 7, 10, 1000, usize::MAX] {
+            for _ in 0..100 {
+                assert!(rng.index(len) < len, "index should be less than {len}");
+            }
+        }
+
+        assert!(
+            (0..100).all(|_| rng.index(1) == 0),
+            "the only valid index for a length of 1 is 0"
+        );
+    }
+
+    #[test]
+    fn biased_sampling_skews_average() {
+        let mut rng = WyRand::new(Default::default());
+
+        let mut average = |draws: u32, max: bool| {
+            let total: u64 = (0..1000)
+                .map(|_| {
+                    if max {
+                        rng.sample_biased_max(0..1000, draws)
+                    } else {
+                        rng.sample_biased_min(0..1000, draws)
+                    }
+                })
+                .sum();
+
+            total / 1000
+        };
+
+        let (min_one, min_two, min_four) =
+            (average(1, false), average(2, false), average(4, false));
+
+        assert!(
+            min_one > min_two && min_two > min_four,
+            "more draws should lower the average: {min_one}, {min_two}, {min_four}"
+        );
+
+        let (max_one, max_four) = (average(1, true), average(4, true));
+
+        assert!(
+            max_one < max_four,
+            "more draws should raise the average: {max_one}, {max_four}"
+        );
+    }
+
+    #[test]
+    fn gen_range_excluding_skips_values() {
+        let mut rng = WyRand::new(Default::default());
+        let exclude = [0, 3, 4, 9, 15];
+        let mut seen = [false; 10];
+
+        for _ in 0..1000 {
+            let value = rng
+                .gen_range_excluding(0..10, &exclude)
+                .expect("values should be available");
+
+            assert!(!exclude.contains(&value), "{value} should be excluded");
+            seen[value as usize] = true;
+        }
+
+        assert_eq!(
+            seen,
+            [false, true, true, false, false, true, true, true, true, false],
+            "every allowed value should eventually be generated"
+        );
+
+        assert_eq!(rng.gen_range_excluding(3..5, &exclude), None);
+        assert_eq!(rng.gen_range_excluding(5..5, &exclude), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = 9..5;
+        assert_eq!(rng.gen_range_excluding(inverted, &exclude), None);
+    }
+
+    #[test]
+    fn choose_covers_every_element() {
+        let mut rng = WyRand::new(Default::default());
+        let mut values = [0u32; 5];
+
+        assert_eq!(rng.choose::<u8>(&[]), None, "empty slices have no choice");
+        assert_eq!(
+            rng.choose_mut::<u8>(&mut []),
+            None,
+            "empty slices have no choice"
+        );
+
+        for _ in 0..1000 {
+            *rng.choose_mut(&mut values).unwrap() += 1;
+        }
+
+        assert!(
+            values.iter().all(|&count| count > 0),
+            "every element should eventually be chosen: {values:?}"
+        );
+
+        let mut seen = [false; 5];
+
+        for _ in 0..1000 {
+            let chosen = rng.choose(&[0usize, 1, 2, 3, 4]).unwrap();
+            seen[*chosen] = true;
+        }
+
+        assert_eq!(seen, [true; 5], "every element should eventually be chosen");
+    }
+
+    #[test]
+    fn choose_weighted_tracks_weights() {
+        let mut rng = WyRand::new(Default::default());
+        let items = [("common", 6u64), ("never", 0), ("rare", 1), ("uncommon", 3)];
+        let mut counts = [0u64; 4];
+
+        for _ in 0..10_000 {
+            let chosen = rng.choose_weighted(&items, |&(_, weight)| weight).unwrap();
+            let position = items.iter().position(|item| item == chosen).unwrap();
+
+            counts[position] += 1;
+        }
+
+        assert_eq!(counts[1], 0, "zero weight items should never be chosen");
+
+        for ((_, weight), count) in items.iter().zip(counts) {
+            let expected = weight * 1000;
+
+            assert!(
+                count.abs_diff(expected) < 300,
+                "{count} should be close to {expected}"
+            );
+        }
+
+        assert_eq!(
+            rng.choose_weighted(&items[1..2], |&(_, weight)| weight),
+            None,
+            "all zero weights should have no choice"
+        );
+        assert_eq!(rng.choose_weighted::<u8>(&[], |_| 1), None);
+    }
+
+    #[test]
+    fn shuffle_permutes_slice() {
+        let mut values = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        WyRand::new(42).shuffle(&mut values);
+
+        assert_eq!(
+            values,
+            [5, 1, 3, 0, 4, 2, 7, 9, 8, 6],
+            "a fixed seed should yield a deterministic permutation"
+        );
+
+        let mut sorted = values;
+        sorted.sort_unstable();
+
+        assert_eq!(
+            sorted,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            "all elements should be kept"
+        );
+
+        let mut rng = WyRand::new(42);
+        let original = rng.clone();
+
+        rng.shuffle::<u8>(&mut []);
+        rng.shuffle(&mut [1]);
+
+        assert_eq!(rng, original, "tiny slices should not consume any values");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn random_bijection_permutations() {
+        let mut rng = WyRand::new(Default::default());
+
+        let mut permutation = rng.random_bijection(100);
+
+        assert_eq!(
+            permutation,
+            WyRand::new(Default::default()).random_bijection(100),
+            "the same seed should yield the same permutation"
+        );
+
+        permutation.sort_unstable();
+
+        assert!(
+            permutation.into_iter().eq(0..100),
+            "the permutation should contain every index exactly once"
+        );
+
+        // Every permutation of 3 elements should be roughly equally likely
+        let mut counts = [0u32; 6];
+
+        for _ in 0..6000 {
+            let slot = match rng.random_bijection(3).as_slice() {
+                [0, 1, 2] => 0,
+                [0, 2, 1] => 1,
+                [1, 0, 2] => 2,
+                [1, 2, 0] => 3,
+                [2, 0, 1] => 4,
+                [2, 1, 0] => 5,
+                other => panic!("{other:?} is not a valid permutation"),
+            };
+            counts[slot] += 1;
+        }
+
+        assert!(
+            counts.iter().all(|&count| (850..1150).contains(&count)),
+            "permutations are not uniformly distributed: {counts:?}"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_time_seeds_differ() {
+        let first = WyRand::from_time();
+
+        let mut rng = WyRand::new(Default::default());
+        (0..1000).for_each(|_| {
+            rng.rand();
+        });
+
+        let second = WyRand::from_time();
+
+        assert_ne!(
+            first, second,
+            "two time seeded instances should not share the same state"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shuffled_order_reproducible() {
+        let order = WyRand::shuffled_order(42, 50);
+
+        assert_eq!(
+            order,
+            WyRand::shuffled_order(42, 50),
+            "the same seed and length should yield the same order"
+        );
+        assert_eq!(order, WyRand::new(42).random_bijection(50));
+
+        let mut sorted = order;
+        sorted.sort_unstable();
+
+        assert!(
+            sorted.into_iter().eq(0..50),
+            "the order should be a valid permutation"
+        );
+
+        // Pinned so the ordering for a seed stays identical across platforms.
+        assert_eq!(
+            WyRand::shuffled_order(42, 64),
+            [
+                36, 29, 38, 42, 17, 18, 53, 50, 37, 26, 39, 58, 60, 33, 1, 59, 2, 52, 7, 45, 22,
+                27, 20, 47, 62, 24, 34, 30, 44, 16, 40, 9, 10, 12, 46, 35, 15, 41, 14, 19, 3, 28,
+                5, 11, 32, 63, 48, 51, 23, 0, 31, 8, 55, 6, 25, 61, 4, 13, 54, 21, 56, 49, 57, 43,
+            ],
+            "a seed and length should always yield the same order"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn random_bitset_density() {
+        let mut rng = WyRand::new(Default::default());
+
+        for density in [0.0, 0.2, 0.25, 0.5, 0.9, 1.0] {
+            let bitset = rng.random_bitset(10_000, density);
+
+            assert_eq!(bitset.len(), 157, "should pack 10000 bits into 157 words");
+            assert_eq!(
+                bitset[156] >> 16,
+                0,
+                "bits past the requested length should be unset"
+            );
+
+            let set: u32 = bitset.iter().map(|word| word.count_ones()).sum();
+            let fraction = f64::from(set) / 10_000.0;
+
+            assert!(
+                (fraction - density).abs() < 0.02,
+                "expected a density of {density}, got {fraction}"
+            );
+        }
+
+        assert!(rng.random_bitset(0, 0.5).is_empty());
+
+        let original = rng.clone();
+
+        for density in [-1.0, 0.0, f64::NAN, 1.0, 2.0] {
+            rng.random_bitset(1000, density);
+        }
+
+        assert_eq!(
+            rng, original,
+            "saturated densities shouldn't generate any values"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn gen_utf8_within_budget() {
+        let mut rng = WyRand::new(Default::default());
+        let mut widths = [false; 4];
+
+        for max_bytes in [0, 1, 3, 16, 256] {
+            for _ in 0..20 {
+                let text = rng.gen_utf8(max_bytes);
+
+                assert!(
+                    text.len() <= max_bytes,
+                    "{text:?} exceeds {max_bytes} bytes"
+                );
+
+                for character in text.chars() {
+                    widths[character.len_utf8() - 1] = true;
+                }
+            }
+        }
+
+        assert_eq!(widths, [true; 4], "all encoded widths should be generated");
+
+        assert_eq!(
+            WyRand::new(42).gen_utf8(64),
+            WyRand::new(42).gen_utf8(64),
+            "the same seed should yield the same string"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn gen_bools_fair() {
+        let mut rng = WyRand::new(Default::default());
+
+        let bools = rng.gen_bools(10_000);
+        let heads = bools.iter().filter(|&&flip| flip).count();
+
+        assert_eq!(bools.len(), 10_000);
+        assert!(
+            (4800..5200).contains(&heads),
+            "expected roughly half to be true, got {heads}"
+        );
+
+        assert_eq!(
+            WyRand::new(Default::default()).gen_bools(10_000),
+            bools,
+            "the same seed should yield the same flips"
+        );
+
+        let mut rng = WyRand::new(Default::default());
+        let mut expected = rng.clone();
+
+        rng.gen_bools(64);
+        expected.rand();
+
+        assert_eq!(
+            rng, expected,
+            "64 flips should only consume a single generated value"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn gen_simplex_sums_to_one() {
+        let mut rng = WyRand::new(Default::default());
+
+        assert!(rng.gen_simplex(0).is_empty());
+        assert_eq!(rng.gen_simplex(1), [1.0]);
+
+        let mut means = [0.0; 3];
+        let mut large_first = 0;
+
+        for _ in 0..10_000 {
+            let simplex = rng.gen_simplex(3);
+
+            assert_eq!(simplex.len(), 3);
+            assert!(simplex.iter().all(|&value| value >= 0.0), "{simplex:?}");
+            assert!(
+                (simplex.iter().sum::<f64>() - 1.0).abs() < 1e-12,
+                "{simplex:?}"
+            );
+
+            for (mean, value) in means.iter_mut().zip(&simplex) {
+                *mean += value / 10_000.0;
+            }
+
+            if simplex[0] > 0.5 {
+                large_first += 1;
+            }
+        }
+
+        // Uniform over the simplex gives each component a mean of 1/3, and a
+        // probability of (1 - 0.5)^2 = 0.25 of a component exceeding 0.5.
+        assert!(
+            means.iter().all(|mean| (mean - 1.0 / 3.0).abs() < 0.01),
+            "component means are not uniform: {means:?}"
+        );
+        assert!(
+            (2_300..2_700).contains(&large_first),
+            "expected roughly 25% above 0.5, got {large_first}"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn choose_multiple_distinct() {
+        let mut rng = WyRand::new(Default::default());
+        let items: alloc::vec::Vec<u32> = (0..20).collect();
+
+        let chosen = rng.choose_multiple(&items, 5);
+
+        assert_eq!(chosen.len(), 5, "should choose exactly `k` elements");
+        assert!(
+            chosen.iter().all(|item| items.contains(item)),
+            "chosen elements should come from the slice"
+        );
+
+        let mut values: alloc::vec::Vec<u32> = chosen.iter().map(|&&item| item).collect();
+        values.sort_unstable();
+        values.dedup();
+
+        assert_eq!(values.len(), 5, "chosen elements should be distinct");
+
+        assert_eq!(
+            chosen,
+            WyRand::new(Default::default()).choose_multiple(&items, 5),
+            "the same seed should choose the same elements"
+        );
+
+        assert_eq!(
+            rng.choose_multiple(&items, 50).len(),
+            items.len(),
+            "`k` should be clamped to the length of the slice"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn truncated_normal_within_bounds() {
+        let mut rng = WyRand::new(Default::default());
+        let mut centre = 0;
+        let mut tail = 0;
+
+        for _ in 0..10_000 {
+            let sample = rng.rand_truncated_normal(0.0, 1.0, -2.0, 2.0);
+
+            assert!((-2.0..=2.0).contains(&sample), "{sample} is out of bounds");
+
+            if sample.abs() < 0.5 {
+                centre += 1;
+            } else if sample.abs() >= 1.5 {
+                tail += 1;
+            }
+        }
+
+        assert!(
+            centre > tail * 3,
+            "samples should cluster around the mean: {centre} vs {tail}"
+        );
+
+        // An interval with negligible probability mass falls back to uniform sampling
+        for _ in 0..10 {
+            let sample = rng.rand_truncated_normal(0.0, 1.0, 50.0, 51.0);
+
+            assert!((50.0..=51.0).contains(&sample), "{sample} is out of bounds");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn weighted_reservoir_favours_heavy_items() {
+        let mut rng = WyRand::new(Default::default());
+        let mut counts = [0u32; 4];
+
+        for _ in 0..10_000 {
+            let weights = [(1, 0), (1, 1), (8, 2), (0, 3)];
+            let sample = rng.weighted_reservoir(weights.into_iter(), 1);
+
+            assert_eq!(sample.len(), 1, "should select exactly `k` items");
+
+            for item in sample {
+                counts[item] += 1;
+            }
+        }
+
+        assert_eq!(counts[3], 0, "zero weight items should never be selected");
+        assert!(
+            counts[2] > counts[0] * 4 && counts[2] > counts[1] * 4,
+            "high weight items should be over-represented: {counts:?}"
+        );
+        assert!(
+            rng.weighted_reservoir([(1, 'a')].into_iter(), 5) == ['a'],
+            "short streams should return every weighted item"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn gen_normal_matches_parameters() {
+        const SAMPLES: usize = 100_000;
+
+        let mut rng = WyRand::new(Default::default());
+        let samples: Vec<f64> = (0..SAMPLES).map(|_| rng.gen_normal(5.0, 2.0)).collect();
+
+        let mean = samples.iter().sum::<f64>() / SAMPLES as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (SAMPLES - 1) as f64;
+
+        assert!(
+            samples.iter().all(|x| x.is_finite()),
+            "samples should be finite"
+        );
+        assert!(
+            (mean - 5.0).abs() < 0.05,
+            "mean {mean} should be close to 5"
+        );
+        assert!(
+            (variance.sqrt() - 2.0).abs() < 0.05,
+            "standard deviation {} should be close to 2",
+            variance.sqrt()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn proptest_bytes_favours_edge_lengths() {
+        let mut rng = WyRand::new(Default::default());
+        let mut counts = [0u32; 101];
+
+        for _ in 0..10_000 {
+            let bytes = rng.proptest_bytes(100);
+
+            assert!(bytes.len() <= 100, "{} exceeds the maximum", bytes.len());
+            counts[bytes.len()] += 1;
+        }
+
+        // A uniform length alone would land on each value around 50 times.
+        for len in [0, 1, 16, 47, 48, 49, 100] {
+            assert!(
+                counts[len] > 400,
+                "edge length {len} should be elevated: {}",
+                counts[len]
+            );
+        }
+
+        assert!(counts[50] < 100, "other lengths shouldn't be elevated");
+        assert!(
+            (0..100).all(|_| rng.proptest_bytes(0).is_empty()),
+            "a maximum of 0 should only give empty vectors"
+        );
+        assert!((0..100).all(|_| rng.proptest_bytes(20).len() <= 20));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn weighted_order_int_reproducible() {
+        let weights = [5, 0, 1, 10, 0, 3];
+        let order = WyRand::new(42).weighted_order_int(&weights);
+
+        assert_eq!(
+            order,
+            [3, 5, 0, 2, 1, 4],
+            "a fixed seed should yield a fixed ordering on every platform"
+        );
+
+        let mut rng = WyRand::new(Default::default());
+        let mut heavy_first = 0;
+
+        for _ in 0..1000 {
+            let order = rng.weighted_order_int(&weights);
+
+            assert_eq!(&order[4..], [1, 4], "zero weights should be placed last");
+            heavy_first += u32::from(order[0] == 3);
+        }
+
+        assert!(
+            (450..600).contains(&heavy_first),
+            "the heaviest index should lead about 10 / 19 of the time: {heavy_first}"
+        );
+        assert!(rng.weighted_order_int(&[]).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn gen_system_time_within_range() {
+        use std::time::{Duration, SystemTime};
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let end = start + Duration::from_secs(86_400);
+        let mut rng = WyRand::new(Default::default());
+
+        for _ in 0..100 {
+            let time = rng.gen_system_time(start, end);
+
+            assert!(start <= time && time < end, "{time:?} is out of range");
+        }
+
+        assert_eq!(
+            WyRand::new(42).gen_system_time(start, end),
+            WyRand::new(42).gen_system_time(start, end),
+            "the same seed should yield the same instant"
+        );
+        assert_eq!(rng.gen_system_time(end, start), end);
+        assert_eq!(rng.gen_system_time(start, start), start);
+    }
+
+    #[cfg(feature = "entropy")]
+    #[test]
+    fn from_entropy_seeds_differ() {
+        let first = WyRand::from_entropy();
+        let second = WyRand::try_from_entropy().expect("entropy should be available");
+
+        assert_ne!(
+            first, second,
+            "two entropy seeded instances should not share the same state"
+        );
+    }
+
+    #[cfg(feature = "entropy")]
+    #[test]
+    fn reseed_from_entropy_changes_state() {
+        let original = WyRand::new(Default::default());
+        let mut rng = original.clone();
+
+        rng.reseed_from_entropy();
+
+        assert_ne!(
+            original, rng,
+            "the state should have been replaced after reseeding"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn stratified_one_per_stratum() {
+        let mut rng = WyRand::new(Default::default());
+
+        for n in [1, 2, 10, 1000] {
+            let samples = rng.stratified(n);
+
+            assert_eq!(samples.len(), n, "should produce exactly `n` samples");
+
+            for (i, sample) in samples.into_iter().enumerate() {
+                let lower = i as f64 / n as f64;
+                let upper = (i + 1) as f64 / n as f64;
+
+                assert!(
+                    (lower..upper).contains(&sample),
+                    "sample {sample} is not within stratum {i} of {n}"
+                );
+                assert!((0.0..1.0).contains(&sample));
+            }
+        }
+    }
+
+    #[test]
+    fn random_enum_variants_reachable() {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            Idle,
+            Walking,
+            Running,
+        }
+
+        random_enum!(State {
+            Idle,
+            Walking,
+            Running
+        });
+
+        let mut rng = WyRand::new(Default::default());
+        let mut seen = [false; 3];
+
+        for _ in 0..100 {
+            match State::gen_random(&mut rng) {
+                State::Idle => seen[0] = true,
+                State::Walking => seen[1] = true,
+                State::Running => seen[2] = true,
+            }
+        }
+
+        assert_eq!(seen, [true; 3], "all variants should be reachable");
+    }
+
+    #[test]
+    fn random_fields_populated() {
+        #[derive(Debug, PartialEq)]
+        struct Fixture {
+            id: u64,
+            weight: f64,
+            flag: bool,
+            pair: (u8, i16),
+        }
+
+        random_fields!(Fixture {
+            id,
+            weight,
+            flag,
+            pair
+        });
+
+        let mut rng = WyRand::new(42);
+        let fixture: Fixture = rng.random();
+
+        let mut expected = WyRand::new(42);
+
+        assert_eq!(
+            fixture,
+            Fixture {
+                id: expected.random(),
+                weight: expected.random(),
+                flag: expected.random(),
+                pair: expected.random(),
+            },
+            "fields should be generated in the order they are listed"
+        );
+        assert_ne!(
+            fixture,
+            rng.random::<Fixture>(),
+            "successive fixtures should differ"
+        );
+    }
+
+    #[test]
+    fn collect_array_deterministic() {
+        let mut rng = WyRand::new(42);
+        let floats: [f32; 8] = rng.collect_array();
+        let flags: [bool; 16] = rng.collect_array();
+
+        let mut expected = WyRand::new(42);
+
+        for float in floats {
+            assert!((0.0..1.0).contains(&float), "{float} is out of range");
+            assert_eq!(float, expected.random::<f32>());
+        }
+
+        for flag in flags {
+            assert_eq!(flag, expected.random::<bool>());
+        }
+
+        assert!(
+            flags.contains(&true) && flags.contains(&false),
+            "flags should not all be the same"
+        );
+    }
+
+    #[test]
+    fn random_tuples() {
+        let mut rng = WyRand::new(Default::default());
+        let mut flags = [false; 2];
+        let mut signs = [false; 2];
+
+        for _ in 0..100 {
+            let (signed, flag, unit): (i8, bool, f64) = rng.random();
+
+            assert!((0.0..1.0).contains(&unit), "{unit} is not within [0, 1)");
+            flags[usize::from(flag)] = true;
+            signs[usize::from(signed < 0)] = true;
+        }
+
+        assert_eq!(flags, [true; 2], "both bool values should be generated");
+        assert_eq!(signs, [true; 2], "signed values should cover both signs");
+
+        let mut other = WyRand::new(42);
+        let mut rng = WyRand::new(42);
+
+        assert_eq!(
+            rng.random::<(u64, u32)>(),
+            (other.rand(), (other.rand() >> 32) as u32),
+            "tuple components should be generated in order from the high bits"
+        );
+    }
+
+    #[test]
+    fn gen_nonzero_values() {
+        let mut rng = WyRand::new(Default::default());
+
+        for _ in 0..10_000 {
+            let wide: NonZeroU64 = rng.gen_nonzero_u64();
+            let narrow: NonZeroU32 = rng.gen_nonzero_u32();
+
+            assert_ne!(wide.get(), 0);
+            assert_ne!(narrow.get(), 0);
+        }
+    }
+
+    #[test]
+    fn next_bits_width() {
+        let mut rng = WyRand::new(Default::default());
+
+        for bits in 1..64 {
+            assert!(
+                rng.next_bits(bits) < 1 << bits,
+                "value should fit in {bits} bits"
+            );
+        }
+
+        let mut other = WyRand::new(Default::default());
+        let mut rng = WyRand::new(Default::default());
+
+        assert_eq!(rng.next_bits(64), other.rand());
+        assert_eq!(rng.next_bits(12), other.rand() >> 52);
+    }
+
+    #[test]
+    fn gen_bool_probabilities() {
+        let mut rng = WyRand::new(Default::default());
+
+        assert!(
+            (0..10_000).all(|_| !rng.gen_bool(0.0)),
+            "a probability of 0.0 should never be true"
+        );
+        assert!(
+            (0..10_000).all(|_| rng.gen_bool(1.0)),
+            "a probability of 1.0 should always be true"
+        );
+
+        let hits = (0..10_000).filter(|_| rng.gen_bool(0.3)).count();
+
+        assert!(
+            (2_800..3_200).contains(&hits),
+            "expected roughly 30% to be true, got {hits}"
+        );
+    }
+
+    #[test]
+    fn gen_floats_within_unit_interval() {
+        let mut rng = WyRand::new(Default::default());
+
+        for _ in 0..100_000 {
+            let double = rng.gen_f64();
+            let single = rng.gen_f32();
+
+            assert!(
+                (0.0..1.0).contains(&double),
+                "{double} is not within [0, 1)"
+            );
+            assert!(
+                (0.0..1.0).contains(&single),
+                "{single} is not within [0, 1)"
+            );
+        }
+
+        // The largest possible values stay below 1.0
+        assert!(((u64::MAX >> 11) as f64 * (1.0 / (1u64 << 53) as f64)) < 1.0);
+        assert!(((u64::MAX >> 40) as f32 * (1.0 / (1u32 << 24) as f32)) < 1.0);
+    }
+
+    #[test]
+    fn sample_cdf_frequencies() {
+        let mut rng = WyRand::new(Default::default());
+        let cdf = [0.1, 0.4, 0.4, 1.0];
+        let mut counts = [0u32; 4];
+
+        for _ in 0..100_000 {
+            counts[rng.sample_cdf(&cdf)] += 1;
+        }
+
+        let frequencies = counts.map(|count| f64::from(count) / 100_000.0);
+
+        for (frequency, expected) in frequencies.into_iter().zip([0.1, 0.3, 0.0, 0.6]) {
+            assert!(
+                (frequency - expected).abs() < 0.01,
+                "frequencies {frequencies:?} don't match the implied PMF"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample from an empty CDF")]
+    fn sample_cdf_empty_panics() {
+        WyRand::new(Default::default()).sample_cdf(&[]);
+    }
+
+    #[test]
+    fn choose_from_values() {
+        let mut rng = WyRand::new(Default::default());
+        let values = [10, 20, 30];
+        let mut seen = [false; 3];
+
+        for _ in 0..100 {
+            match rng.choose_from(&values) {
+                Some(10) => seen[0] = true,
+                Some(20) => seen[1] = true,
+                Some(30) => seen[2] = true,
+                other => panic!("{other:?} was not one of the provided values"),
+            }
+        }
+
+        assert_eq!(seen, [true; 3], "every value should eventually be selected");
+        assert_eq!(rng.choose_from::<u32>(&[]), None);
+    }
+
+    #[test]
+    fn antithetic_variance_reduction() {
+        let mut rng = WyRand::new(Default::default());
+
+        let variance = |estimates: &[f64]| {
+            let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+
+            estimates
+                .iter()
+                .map(|e| (e - mean) * (e - mean))
+                .sum::<f64>()
+                / estimates.len() as f64
+        };
+
+        let mut antithetic = [0.0; 200];
+        let mut independent = [0.0; 200];
+
+        // Estimate the integral of x^2 over [0, 1) with 100 values per estimate
+        for (antithetic, independent) in antithetic.iter_mut().zip(independent.iter_mut()) {
+            *antithetic = (0..50)
+                .map(|_| {
+                    let (a, b) = rng.rand_f64_antithetic();
+
+                    assert!((a + b - 1.0).abs() < 1e-12, "the pair should sum to 1.0");
+
+                    a * a + b * b
+                })
+                .sum::<f64>()
+                / 100.0;
+            *independent = (0..100)
+                .map(|_| {
+                    let value = rng.gen_f64();
+                    value * value
+                })
+                .sum::<f64>()
+                / 100.0;
+        }
+
+        assert!(
+            variance(&antithetic) < variance(&independent),
+            "antithetic sampling should reduce the variance of the estimate"
+        );
+    }
+
+    #[test]
+    fn stream_matches_rand() {
+        let mut rng = WyRand::new(42);
+        let expected: alloc::vec::Vec<u64> = (0..16).map(|_| rng.rand()).collect();
+
+        let mut stream = WyRand::new(42).into_stream();
+        let values: alloc::vec::Vec<u64> = stream.by_ref().take(16).collect();
+
+        assert_eq!(values, expected, "the stream should match calling `rand`");
+        assert_eq!(
+            stream.into_inner(),
+            rng,
+            "the inner generator should have advanced with the stream"
+        );
+    }
+
+    #[test]
+    fn sample_range_no_replace_distinct() {
+        let mut rng = WyRand::new(Default::default());
+
+        for (n, k) in [(0, 5), (1, 1), (10, 10), (1000, 300), (1000, 2000)] {
+            let mut values: alloc::vec::Vec<u64> = rng.sample_range_no_replace(n, k).collect();
+
+            assert_eq!(values.len() as u64, k.min(n), "should yield `k` values");
+            assert!(
+                values.iter().all(|&value| value < n),
+                "values should be in range"
+            );
+
+            values.sort_unstable();
+            values.dedup();
+
+            assert_eq!(values.len() as u64, k.min(n), "values should be distinct");
+        }
+
+        assert!(
+            WyRand::new(42)
+                .sample_range_no_replace(100, 20)
+                .eq(WyRand::new(42).sample_range_no_replace(100, 20)),
+            "the same seed should yield the same values"
+        );
+    }
+
+    #[test]
+    fn sample_range_no_replace_covers_subsets() {
+        let mut rng = WyRand::new(Default::default());
+        let mut pairs = [false; 64];
+        let mut subsets = [false; 64];
+        let mut same_parity = false;
+
+        for _ in 0..20_000 {
+            let pair = rng
+                .sample_range_no_replace(8, 2)
+                .fold(0u64, |set, value| set | 1 << value);
+            let subset = rng
+                .sample_range_no_replace(6, 3)
+                .fold(0u64, |set, value| set | 1 << value);
+            let mut large = rng.sample_range_no_replace(1024, 2);
+
+            pairs[pair.trailing_zeros() as usize * 8 + (63 - pair.leading_zeros()) as usize] = true;
+            subsets[subset as usize] = true;
+            same_parity |= large.next().unwrap() % 2 == large.next().unwrap() % 2;
+        }
+
+        assert_eq!(
+            pairs.iter().filter(|&&seen| seen).count(),
+            28,
+            "every pair from 0..8 should be reachable"
+        );
+        assert_eq!(
+            subsets.iter().filter(|&&seen| seen).count(),
+            20,
+            "every 3-subset of 0..6 should be reachable"
+        );
+        assert!(same_parity, "values shouldn't be constrained by parity");
+    }
+
+    #[test]
+    fn choose_with_index_points_to_element() {
+        let mut rng = WyRand::new(Default::default());
+        let items = ["a", "b", "c", "d"];
+
+        for _ in 0..100 {
+            let (index, item) = rng.choose_with_index(&items).expect("slice is not empty");
+
+            assert!(index < items.len(), "{index} is out of bounds");
+            assert_eq!(
+                &items[index], item,
+                "index should point to the chosen element"
+            );
+        }
+
+        assert_eq!(rng.choose_with_index::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn iter_combinators() {
+        let mut rng = WyRand::new(Default::default());
+        let mut other = WyRand::new(Default::default());
+
+        let expected = [other.rand() % 10, other.rand() % 10, other.rand() % 10];
+
+        assert!(
+            rng.iter().map(|value| value % 10).take(3).eq(expected),
+            "the iterator should yield the same stream as calling `rand`"
+        );
+
+        // The iterator only borrows the generator, so it can keep being used afterwards
+        assert_eq!(rng.rand(), other.rand());
+
+        assert!(
+            rng.iter().zip(other.iter()).take(5).all(|(a, b)| a == b),
+            "two generators with the same state should yield the same stream"
+        );
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn rand_fill_integration() {
+        use rand::Rng;
+
+        let mut first = [0u32; 8];
+        let mut second = [0u32; 8];
+
+        WyRand::from_seed(Default::default()).fill(&mut first);
+        WyRand::from_seed(Default::default()).fill(&mut second);
+
+        assert_eq!(first, second, "the same seed should fill the same values");
+        assert_ne!(first, [0; 8], "the array should have been filled");
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn fill_bytes_whole_words() {
+        use rand_core::impls::fill_bytes_via_next;
+
+        for len in [0, 1, 7, 8, 9, 4096] {
+            let mut rng = WyRand::new(42);
+            let mut other = WyRand::new(42);
+
+            let mut bytes = alloc::vec![0u8; len];
+            rng.fill_bytes(&mut bytes);
+
+            let expected: alloc::vec::Vec<u8> = (0..(len + 7) / 8)
+                .flat_map(|_| other.rand().to_le_bytes())
+                .take(len)
+                .collect();
+
+            assert_eq!(
+                bytes, expected,
+                "filling {len} bytes should use whole words"
+            );
+            assert_eq!(rng, other, "one value should be generated per started word");
+
+            let mut via_next = alloc::vec![0u8; len];
+            fill_bytes_via_next(&mut WyRand::new(42), &mut via_next);
+
+            assert_eq!(bytes, via_next, "output should match `fill_bytes_via_next`");
+        }
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn spawn_distinct_generators() {
+        let mut source = WyRand::new(Default::default());
+
+        let mut outputs: alloc::vec::Vec<u64> = (0..100)
+            .map(|_| WyRand::spawn(&mut source).rand())
+            .collect();
+
+        outputs.sort_unstable();
+        outputs.dedup();
+
+        assert_eq!(
+            outputs.len(),
+            100,
+            "spawned generators should have distinct first outputs"
+        );
+    }
+
+    #[cfg(all(feature = "serde1", feature = "debug"))]
+    #[test]
+    fn serde_tokens() {
+        use serde_test::{assert_tokens, Token};
+
+        let seed = 12345;
+        let rng = WyRand::new(seed);
+
+        // Serialized as a bare `u64` rather than a struct with a named field
+        assert_tokens(&rng, &[Token::U64(seed)]);
     }
 
     #[cfg(feature = "hash")]